@@ -1,4 +1,27 @@
 // use pyo3::prelude::*; (commented out as this is a C-compatible FFI implementation)
+// These functions are `extern "C"` FFI entry points: the pointers they
+// dereference come from the Python caller by contract, not from safe Rust
+// callers, so clippy's usual "mark it unsafe" advice doesn't apply here.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use rayon::prelude::*;
+use std::sync::OnceLock;
+
+/// Byte-per-candidate Sieve of Eratosthenes. For `i >= 2`, `result[i]` is
+/// true iff `i` is prime; entries 0 and 1 are left true, so callers skip
+/// them. Shared by every FFI entry point that needs a plain (not
+/// bit-packed) sieve up to a modest limit.
+fn sieve_bool_array(limit: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; limit + 1];
+    for p in 2..=((limit as f64).sqrt() as usize) {
+        if is_prime[p] {
+            for multiple in (p * p..=limit).step_by(p) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    is_prime
+}
 
 /// FFI-exported C-compatible Sieve of Eratosthenes.
 /// Fills `primes_out` with primes <= n and returns the number of primes found.
@@ -8,26 +31,413 @@ pub extern "C" fn sieve_rs(n: i32, primes_out: *mut i32) -> i32 {
         return 0;
     }
     let limit = n as usize;
-    let mut is_prime = vec![true; limit + 1];
+    let is_prime = sieve_bool_array(limit);
 
-    for p in 2..=((limit as f64).sqrt() as usize) {
-        if is_prime[p] {
-            for multiple in (p * p..=limit).step_by(p) {
-                is_prime[multiple] = false;
+    let mut count = 0;
+    unsafe {
+        for (i, &is_p) in is_prime.iter().enumerate().skip(2) {
+            if is_p {
+                *primes_out.add(count) = i as i32;
+                count += 1;
             }
         }
     }
+    count as i32
+}
 
-    let mut count = 0;
+/// Number of `u32` words per cache-sized processing window (32 KiB).
+const SEGMENT_WINDOW_WORDS: usize = 32 * 1024 / 4;
+
+/// Sieve the primes up to `limit`. Used to seed `sieve_segmented_rs` and
+/// `sieve_parallel_rs` with the small primes up to `sqrt(n)`, to build
+/// the `prime_pi_rs` cache, and by `nth_prime_rs` to sieve up to its
+/// upper bound on each call (that result is not cached).
+fn seed_primes(limit: i64) -> Vec<i64> {
+    if limit < 2 {
+        return Vec::new();
+    }
+    let limit = limit as usize;
+    let is_prime = sieve_bool_array(limit);
+    (2..=limit).filter(|&i| is_prime[i]).map(|i| i as i64).collect()
+}
+
+#[inline]
+fn bit_is_set(words: &[u32], idx: usize) -> bool {
+    (words[idx / 32] >> (idx % 32)) & 1 == 1
+}
+
+#[inline]
+fn set_bit(words: &mut [u32], idx: usize) {
+    words[idx / 32] |= 1 << (idx % 32);
+}
+
+/// FFI-exported bit-packed, odds-only segmented Sieve of Eratosthenes.
+/// Index `i` represents `2*i + 3`, packed 32 per `u32`, and composites are
+/// culled window-by-window in `SEGMENT_WINDOW_WORDS`-sized slices so each
+/// sweep stays cache-hot. Fills `primes_out` (2 followed by the odd
+/// primes) and returns the count.
+#[no_mangle]
+pub extern "C" fn sieve_segmented_rs(n: i64, primes_out: *mut i64) -> i64 {
+    if n < 2 {
+        return 0;
+    }
+    let mut count: usize = 0;
     unsafe {
-        for i in 2..=limit {
-            if is_prime[i] {
-                *primes_out.add(count) = i as i32;
+        *primes_out.add(count) = 2;
+    }
+    count += 1;
+    if n < 3 {
+        return count as i64;
+    }
+
+    let last_ndx = ((n - 3) / 2) as usize;
+    let num_bits = last_ndx + 1;
+    let num_words = num_bits / 32 + 1;
+    let mut words = vec![0u32; num_words];
+
+    let sqrt_n = (n as f64).sqrt() as i64;
+    let seeds = seed_primes(sqrt_n);
+
+    for window_start in (0..num_words).step_by(SEGMENT_WINDOW_WORDS) {
+        let window_end_word = (window_start + SEGMENT_WINDOW_WORDS).min(num_words);
+        let lo_ndx = window_start * 32;
+        let hi_ndx = (window_end_word * 32).min(num_bits);
+
+        for &p in &seeds {
+            if p < 3 {
+                continue;
+            }
+            let start_ndx = ((p * p - 3) / 2) as usize;
+            if start_ndx >= hi_ndx {
+                continue;
+            }
+            let p = p as usize;
+            let first = if start_ndx >= lo_ndx {
+                start_ndx
+            } else {
+                let diff = lo_ndx - start_ndx;
+                let rem = diff % p;
+                if rem == 0 {
+                    lo_ndx
+                } else {
+                    lo_ndx + (p - rem)
+                }
+            };
+            let mut ndx = first;
+            while ndx < hi_ndx {
+                set_bit(&mut words, ndx);
+                ndx += p;
+            }
+        }
+
+        for ndx in lo_ndx..hi_ndx {
+            if !bit_is_set(&words, ndx) {
+                unsafe {
+                    *primes_out.add(count) = 2 * ndx as i64 + 3;
+                }
                 count += 1;
             }
         }
     }
-    count as i32
+
+    count as i64
+}
+
+/// Size of each independently-sieved window for `sieve_parallel_rs`.
+const PARALLEL_CHUNK_SIZE: i64 = 100_000;
+
+/// FFI-exported parallel chunked Sieve of Eratosthenes using rayon.
+/// The range `[2, n]` is split into `PARALLEL_CHUNK_SIZE` windows; each
+/// window independently marks multiples of the pre-computed seed primes
+/// up to `sqrt(n)` within its own `[lo, hi)` range, and results are
+/// concatenated in order into `primes_out`.
+#[no_mangle]
+pub extern "C" fn sieve_parallel_rs(n: i64, primes_out: *mut i64) -> i64 {
+    if n < 2 {
+        return 0;
+    }
+
+    let sqrt_n = (n as f64).sqrt() as i64;
+    let seeds = seed_primes(sqrt_n);
+
+    let mut windows = Vec::new();
+    let mut lo = 2i64;
+    while lo <= n {
+        let hi = (lo + PARALLEL_CHUNK_SIZE).min(n + 1);
+        windows.push((lo, hi));
+        lo = hi;
+    }
+
+    let results: Vec<Vec<i64>> = windows
+        .par_iter()
+        .map(|&(lo, hi)| {
+            let mut is_prime = vec![true; (hi - lo) as usize];
+            for &p in &seeds {
+                let start = (p * p).max(((lo + p - 1) / p) * p);
+                if start >= hi {
+                    continue;
+                }
+                let mut m = start;
+                while m < hi {
+                    is_prime[(m - lo) as usize] = false;
+                    m += p;
+                }
+            }
+            is_prime
+                .iter()
+                .enumerate()
+                .filter(|&(_, &is_p)| is_p)
+                .map(|(idx, _)| lo + idx as i64)
+                .collect()
+        })
+        .collect();
+
+    let mut count: usize = 0;
+    unsafe {
+        for window in results {
+            for prime in window {
+                *primes_out.add(count) = prime;
+                count += 1;
+            }
+        }
+    }
+    count as i64
+}
+
+/// Upper bound (inclusive) of the cache used by `prime_pi_rs`.
+const PRIME_PI_CACHE_LIMIT: i64 = 1_000_000;
+
+/// Lazily-initialised cache of primes up to `PRIME_PI_CACHE_LIMIT`,
+/// built once via the sieve and reused across calls.
+static PRIME_PI_CACHE: OnceLock<Vec<i64>> = OnceLock::new();
+
+fn cached_primes() -> &'static Vec<i64> {
+    PRIME_PI_CACHE.get_or_init(|| seed_primes(PRIME_PI_CACHE_LIMIT))
+}
+
+/// FFI-exported prime-counting function pi(x): the number of primes <= x.
+/// Binary-searches the cached primes up to `PRIME_PI_CACHE_LIMIT`; beyond
+/// that, returns the logarithmic estimate `x / (ln x - 1)` instead of
+/// failing.
+#[no_mangle]
+pub extern "C" fn prime_pi_rs(x: i64) -> i64 {
+    if x < 2 {
+        return 0;
+    }
+    if x <= PRIME_PI_CACHE_LIMIT {
+        let primes = cached_primes();
+        return match primes.binary_search(&x) {
+            Ok(idx) => (idx + 1) as i64,
+            Err(idx) => idx as i64,
+        };
+    }
+    let x_f = x as f64;
+    (x_f / (x_f.ln() - 1.0)).round() as i64
+}
+
+/// Hardcoded n-th prime for n < 6, where the analytic bound below is not
+/// an over-estimate (it falls below the n-th prime for n = 2, 3 and is
+/// not finite for n = 1).
+const SMALL_PRIMES: [i64; 5] = [2, 3, 5, 7, 11];
+
+/// Upper bound for the n-th prime (1-indexed) via `n * (ln n + ln ln n)`
+/// for n >= 6. This is a proven over-estimate, so sieving up to it is
+/// guaranteed to contain at least n primes.
+fn nth_prime_upper_bound(n: i64) -> i64 {
+    if (n as usize) <= SMALL_PRIMES.len() {
+        return SMALL_PRIMES[(n - 1) as usize];
+    }
+    let n_f = n as f64;
+    (n_f * (n_f.ln() + n_f.ln().ln())).ceil() as i64
+}
+
+/// FFI-exported n-th prime (1-indexed) query. Sieves up to
+/// `nth_prime_upper_bound(n)` once and indexes into the result.
+#[no_mangle]
+pub extern "C" fn nth_prime_rs(n: i64) -> i64 {
+    if n < 1 {
+        return 0;
+    }
+    let bound = nth_prime_upper_bound(n);
+    let primes = seed_primes(bound);
+    primes[(n - 1) as usize]
+}
+
+/// FFI-exported, bounds-safe Sieve of Eratosthenes.
+///
+/// Unlike `sieve_rs`, which writes into `primes_out` with no knowledge of
+/// the buffer's length, this variant takes the caller's capacity
+/// `out_cap`, writes at most `out_cap` entries, and always stores the
+/// true prime count in `*out_written` even when it exceeds capacity.
+/// Returns `0` on success, `-1` if `out_cap` was too small to hold every
+/// prime (the caller can reallocate using `*out_written` and retry), or
+/// `-2` if `primes_out` or `out_written` is null. This gives the Python
+/// side a defined error contract instead of silent memory corruption.
+#[no_mangle]
+pub extern "C" fn sieve_checked_rs(
+    n: i32,
+    primes_out: *mut i32,
+    out_cap: usize,
+    out_written: *mut usize,
+) -> i32 {
+    if primes_out.is_null() || out_written.is_null() {
+        return -2;
+    }
+    if n < 2 {
+        unsafe {
+            *out_written = 0;
+        }
+        return 0;
+    }
+
+    let limit = n as usize;
+    let is_prime = sieve_bool_array(limit);
+
+    let true_count = (2..=limit).filter(|&i| is_prime[i]).count();
+    unsafe {
+        *out_written = true_count;
+    }
+
+    let to_write = true_count.min(out_cap);
+    let mut written = 0usize;
+    unsafe {
+        for (i, &is_p) in is_prime.iter().enumerate().skip(2) {
+            if written >= to_write {
+                break;
+            }
+            if is_p {
+                *primes_out.add(written) = i as i32;
+                written += 1;
+            }
+        }
+    }
+
+    if true_count > out_cap {
+        -1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force_primes(n: i64) -> Vec<i64> {
+        if n < 2 {
+            return Vec::new();
+        }
+        let limit = n as usize;
+        let mut is_prime = vec![true; limit + 1];
+        for p in 2..=((limit as f64).sqrt() as usize) {
+            if is_prime[p] {
+                for multiple in (p * p..=limit).step_by(p) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+        (2..=limit).filter(|&i| is_prime[i]).map(|i| i as i64).collect()
+    }
+
+    #[test]
+    fn sieve_segmented_matches_brute_force_across_window_boundary() {
+        // 600_000 spans more than one SEGMENT_WINDOW_WORDS window, so this
+        // also exercises the window-boundary marking logic.
+        for &n in &[0i64, 1, 2, 3, 4, 10, 97, 1000, 600_000] {
+            let mut buf = vec![0i64; brute_force_primes(n).len() + 1];
+            let count = sieve_segmented_rs(n, buf.as_mut_ptr());
+            assert_eq!(&buf[..count as usize], brute_force_primes(n).as_slice(), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn sieve_parallel_matches_brute_force_across_chunk_boundary() {
+        // Windows start at 2, so 100_000 still fits in the first window
+        // (which ends at 100_002) and 250_000 spans three windows,
+        // exercising the per-window start-offset calculation at chunk edges.
+        for &n in &[0i64, 1, 2, 3, 4, 10, 97, 1000, 100_000, 250_000] {
+            let mut buf = vec![0i64; brute_force_primes(n).len() + 1];
+            let count = sieve_parallel_rs(n, buf.as_mut_ptr());
+            assert_eq!(&buf[..count as usize], brute_force_primes(n).as_slice(), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn prime_pi_matches_brute_force_within_cache() {
+        for &x in &[0i64, 1, 2, 3, 10, 100, 7919, 10_000] {
+            let expected = brute_force_primes(x).len() as i64;
+            assert_eq!(prime_pi_rs(x), expected, "x = {x}");
+        }
+        // known value at the cache's upper edge: pi(1_000_000) = 78498
+        assert_eq!(prime_pi_rs(PRIME_PI_CACHE_LIMIT), 78498);
+    }
+
+    #[test]
+    fn prime_pi_falls_back_to_analytic_estimate_beyond_cache() {
+        let x = PRIME_PI_CACHE_LIMIT + 1;
+        let x_f = x as f64;
+        let expected = (x_f / (x_f.ln() - 1.0)).round() as i64;
+        assert_eq!(prime_pi_rs(x), expected);
+    }
+
+    #[test]
+    fn nth_prime_matches_known_values_across_small_n_table_boundary() {
+        // n = 5 is the last hardcoded table entry, n = 6 the first via the
+        // analytic bound.
+        let known = [
+            (1, 2),
+            (2, 3),
+            (3, 5),
+            (4, 7),
+            (5, 11),
+            (6, 13),
+            (10, 29),
+            (100, 541),
+            (1000, 7919),
+        ];
+        for (n, expected) in known {
+            assert_eq!(nth_prime_rs(n), expected, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn sieve_checked_succeeds_when_capacity_is_sufficient() {
+        let expected = brute_force_primes(100);
+        let mut buf = vec![0i32; expected.len()];
+        let mut written = 0usize;
+        let code = sieve_checked_rs(100, buf.as_mut_ptr(), buf.len(), &mut written);
+        assert_eq!(code, 0);
+        assert_eq!(written, expected.len());
+        let got: Vec<i64> = buf.iter().map(|&p| p as i64).collect();
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn sieve_checked_truncates_and_errors_when_capacity_too_small() {
+        let expected = brute_force_primes(100);
+        let out_cap = expected.len() - 2;
+        let mut buf = vec![0i32; out_cap];
+        let mut written = 0usize;
+        let code = sieve_checked_rs(100, buf.as_mut_ptr(), out_cap, &mut written);
+        assert_eq!(code, -1);
+        assert_eq!(written, expected.len());
+        let got: Vec<i64> = buf.iter().map(|&p| p as i64).collect();
+        assert_eq!(got, expected[..out_cap]);
+    }
+
+    #[test]
+    fn sieve_checked_rejects_null_pointers() {
+        let mut buf = vec![0i32; 10];
+        let mut written = 0usize;
+        assert_eq!(
+            sieve_checked_rs(100, std::ptr::null_mut(), 10, &mut written),
+            -2
+        );
+        assert_eq!(
+            sieve_checked_rs(100, buf.as_mut_ptr(), 10, std::ptr::null_mut()),
+            -2
+        );
+    }
 }
 
 // /// Pure Rust-to-Python function for PyO3 usage (not using PyO3 for this example)
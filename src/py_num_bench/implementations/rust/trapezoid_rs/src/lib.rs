@@ -1,21 +1,192 @@
 // use pyo3::prelude::*; (commented out as this is a C-compatible FFI implementation)
 
-/// FFI-exported trapezoid integration of f(x) = x^2 on [a,b] with n intervals.
-#[no_mangle]
-pub extern "C" fn trapezoid_rs(a: f64, b: f64, n: i32) -> f64 {
-    let n = n as usize;
+/// Quadrature rule selector for `integrate_rs`.
+const RULE_TRAPEZOID: i32 = 0;
+const RULE_SIMPSON: i32 = 1;
+const RULE_MIDPOINT: i32 = 2;
+
+fn trapezoid(f: extern "C" fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let h = (b - a) / n as f64;
+    let mut sum = 0.5 * (f(a) + f(b));
+    for i in 1..n {
+        let x = a + i as f64 * h;
+        sum += f(x);
+    }
+    sum * h
+}
+
+/// Composite Simpson's rule. Requires an even number of intervals; an odd
+/// `n` is bumped up to the next even number so the weight pattern
+/// (1,4,2,4,...,4,1) stays well-defined.
+fn simpson(f: extern "C" fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    let n = if n % 2 == 1 { n + 1 } else { n };
     if n == 0 {
         return 0.0;
     }
     let h = (b - a) / n as f64;
-    let mut sum = 0.5 * (a * a + b * b);
+    let mut sum = f(a) + f(b);
     for i in 1..n {
         let x = a + i as f64 * h;
-        sum += x * x;
+        let weight = if i % 2 == 0 { 2.0 } else { 4.0 };
+        sum += weight * f(x);
+    }
+    sum * h / 3.0
+}
+
+fn midpoint(f: extern "C" fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let h = (b - a) / n as f64;
+    let mut sum = 0.0;
+    for i in 0..n {
+        let x = a + (i as f64 + 0.5) * h;
+        sum += f(x);
     }
     sum * h
 }
 
+/// FFI-exported numerical integration of an arbitrary `f64 -> f64`
+/// integrand over `[a, b]` with `n` intervals, via `rule`: trapezoid (0),
+/// composite Simpson's (1), or midpoint (2). Unknown `rule` values
+/// return `NAN`.
+#[no_mangle]
+pub extern "C" fn integrate_rs(
+    f: extern "C" fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    n: i32,
+    rule: i32,
+) -> f64 {
+    let n = n as usize;
+    match rule {
+        RULE_TRAPEZOID => trapezoid(f, a, b, n),
+        RULE_SIMPSON => simpson(f, a, b, n),
+        RULE_MIDPOINT => midpoint(f, a, b, n),
+        _ => f64::NAN,
+    }
+}
+
+/// Single-panel Simpson's rule estimate over `[a, b]`.
+fn simpson_estimate(f: extern "C" fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    let m = (a + b) / 2.0;
+    (b - a) / 6.0 * (f(a) + 4.0 * f(m) + f(b))
+}
+
+/// Recursive step of adaptive Simpson's rule. `s_ab` is the caller's
+/// already-computed whole-interval estimate, reused to avoid recomputing
+/// `f` at shared points. Bottoms out at `depth == 0` to guarantee
+/// termination even if `tol` is unreachable due to floating-point noise.
+fn adaptive_simpson(
+    f: extern "C" fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tol: f64,
+    s_ab: f64,
+    depth: i32,
+) -> f64 {
+    let m = (a + b) / 2.0;
+    let s_am = simpson_estimate(f, a, m);
+    let s_mb = simpson_estimate(f, m, b);
+    let diff = s_am + s_mb - s_ab;
+
+    if depth <= 0 || diff.abs() <= 15.0 * tol {
+        return s_am + s_mb + diff / 15.0;
+    }
+
+    adaptive_simpson(f, a, m, tol / 2.0, s_am, depth - 1)
+        + adaptive_simpson(f, m, b, tol / 2.0, s_mb, depth - 1)
+}
+
+/// FFI-exported adaptive Simpson quadrature with an error tolerance,
+/// refining by halves until within `tol` or `max_depth` is reached.
+#[no_mangle]
+pub extern "C" fn integrate_adaptive_rs(
+    f: extern "C" fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_depth: i32,
+) -> f64 {
+    let s_ab = simpson_estimate(f, a, b);
+    adaptive_simpson(f, a, b, tol, s_ab, max_depth)
+}
+
+extern "C" fn x_squared(x: f64) -> f64 {
+    x * x
+}
+
+/// FFI-exported trapezoid integration of f(x) = x^2 on [a,b] with n
+/// intervals. Thin wrapper over `integrate_rs` for backward compatibility.
+#[no_mangle]
+pub extern "C" fn trapezoid_rs(a: f64, b: f64, n: i32) -> f64 {
+    integrate_rs(x_squared, a, b, n, RULE_TRAPEZOID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn sin_fn(x: f64) -> f64 {
+        x.sin()
+    }
+
+    extern "C" fn cube(x: f64) -> f64 {
+        x * x * x
+    }
+
+    #[test]
+    fn integrate_rs_trapezoid_converges_to_closed_form() {
+        let result = integrate_rs(x_squared, 0.0, 1.0, 1000, RULE_TRAPEZOID);
+        assert!((result - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrate_rs_midpoint_converges_to_closed_form() {
+        let result = integrate_rs(x_squared, 0.0, 1.0, 1000, RULE_MIDPOINT);
+        assert!((result - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrate_rs_simpson_is_exact_for_cubics() {
+        // Simpson's rule is exact for polynomials up to degree 3.
+        let result = integrate_rs(cube, 0.0, 2.0, 4, RULE_SIMPSON);
+        assert!((result - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn integrate_rs_simpson_bumps_odd_n_up_to_even() {
+        let result = integrate_rs(cube, 0.0, 2.0, 5, RULE_SIMPSON);
+        assert!((result - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn integrate_rs_unknown_rule_returns_nan() {
+        assert!(integrate_rs(x_squared, 0.0, 1.0, 10, 99).is_nan());
+    }
+
+    #[test]
+    fn integrate_adaptive_matches_closed_form() {
+        let result = integrate_adaptive_rs(x_squared, 0.0, 1.0, 1e-9, 50);
+        assert!((result - 1.0 / 3.0).abs() < 1e-6);
+
+        let result = integrate_adaptive_rs(sin_fn, 0.0, std::f64::consts::PI, 1e-9, 50);
+        assert!((result - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrate_adaptive_terminates_when_depth_is_exhausted() {
+        // Simpson's rule is not exact for sin, so the refinement difference
+        // stays nonzero and tol = 0.0 is never met: this only terminates
+        // via the max_depth bottom-out.
+        let result = integrate_adaptive_rs(sin_fn, 0.0, std::f64::consts::PI, 0.0, 4);
+        assert!(result.is_finite());
+    }
+}
+
 // // Pure Rust-to-Python version for PyO3 usage.
 // #[pyfunction]
 // fn trapezoid_py(a: f64, b: f64, n: i32) -> PyResult<f64> {